@@ -1,14 +1,20 @@
 #![allow(dead_code)]
 
-use std::{fmt::Write as _, fs};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    fmt::Write as _,
+    fs,
+};
 
 use cargo_metadata::{Dependency, DependencyKind, Metadata, Package, PackageId};
-use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input, MultiSelect};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, MultiSelect};
 use itertools::Itertools as _;
 use toml_edit::Document;
 
 mod utils;
 
+use utils::VersionExt as _;
+
 fn main() {
     let mut args = std::env::args().skip_while(|val| !val.starts_with("--manifest-path"));
 
@@ -24,6 +30,15 @@ fn main() {
         None => {}
     };
 
+    // preview planned edits instead of writing them
+    let dry_run = std::env::args().any(|arg| arg == "--dry-run");
+    // don't clobber the clipboard, even outside of a dry run
+    let skip_clipboard = std::env::args().any(|arg| arg == "--skip-clipboard");
+
+    // every manifest edit computed below, recorded regardless of `dry_run` so a dry run can still
+    // ask cargo to resolve against what the manifests *would* look like (see `refresh_lockfile`)
+    let mut pending_manifest_edits: Vec<(cargo_metadata::camino::Utf8PathBuf, String)> = vec![];
+
     let metadata = cmd.exec().unwrap();
 
     let mut members = vec![];
@@ -75,118 +90,719 @@ fn main() {
         .interact()
         .unwrap();
 
-    let (pkg, _dependencies, dependents) = &members[selection];
+    let (pkg, _dependencies, _dependents) = &members[selection];
 
     println!("You chose: {}", pkg.name);
 
-    if let Some(unreleased) = pkg.extract_unreleased() {
+    let unreleased = pkg.extract_unreleased();
+
+    if let Some(unreleased) = &unreleased {
         println!("Changes since {}", pkg.version);
         println!("{unreleased}");
     };
 
-    let new_version = Input::<String>::with_theme(&ColorfulTheme::default())
-        .with_prompt("New version:")
-        .validate_with(|input: &String| -> Result<(), String> {
-            let Ok(v2) = semver::Version::parse(input) else {
-                return Err(format!("{input} is not a valid SemVer string"));
-            };
-
-            if v2 <= pkg.version {
-                return Err("New version must be higher than current version".to_owned());
-            }
-
-            Ok(())
-        })
-        .interact_text()
-        .unwrap();
+    let new_version = select_new_version(pkg, unreleased.as_deref());
+    let new_version = semver::Version::parse(&new_version).unwrap();
 
     let target_manifest = fs::read_to_string(&pkg.manifest_path).unwrap();
     let mut target_manifest = target_manifest.parse::<Document>().unwrap();
 
-    utils::replace_toml_string(
+    utils::replace_toml_string_item(
         &mut target_manifest["package"]["version"],
         &new_version.to_string(),
     );
 
-    fs::write(&pkg.manifest_path, target_manifest.to_string()).unwrap();
+    println!(
+        "in {} manifest, updating package version {} => {new_version}",
+        pkg.manifest_path, pkg.version,
+    );
 
-    match dependents.len() {
-        0 => {}
+    pending_manifest_edits.push((pkg.manifest_path.clone(), target_manifest.to_string()));
 
-        n => {
-            println!(
-                "There are {n} workspace members that depend on {}.",
-                pkg.name
-            );
+    if dry_run {
+        println!("(dry run) not writing {}", pkg.manifest_path);
+    } else {
+        fs::write(&pkg.manifest_path, target_manifest.to_string()).unwrap();
+    }
 
-            let update_items = dependents
-                .iter()
-                .map(|(dependent, dep)| (format!("{dependent} : {}", dep.req), true))
-                .collect::<Vec<_>>();
+    if let Some(promoted) = pkg.promote_unreleased(&new_version, &today()) {
+        let changelog_path = pkg.changelog_path().unwrap();
 
-            let selections = MultiSelect::with_theme(&ColorfulTheme::default())
-                .with_prompt(
-                    "Select the workspace members whose version requirement should be updated to 2:",
-                )
-                .items_checked(&update_items)
-                .interact()
-                .unwrap();
-
-            for selection in selections {
-                let (dependant_name, dep) = &dependents[selection];
-
-                let cur_req = &dep.req;
-                let new_req = match utils::updated_req(
-                    cur_req,
-                    &pkg.version,
-                    &semver::Version::parse(&new_version).unwrap(),
-                ) {
-                    utils::SemverUpdateKind::CurrentRequirementDoesNotMatchVersion => {
-                        eprintln!("CurrentRequirementDoesNotMatchVersion so not touching requirement on this crate");
-                        continue;
-                    }
-                    utils::SemverUpdateKind::ExistingReqCompatible => {
-                        eprintln!("ExistingReqCompatible so leaving it alone");
-                        continue;
-                    }
-                    utils::SemverUpdateKind::UpdateReq(req) => req,
-                };
+        println!("Promoting Unreleased section in {changelog_path} to {new_version}");
 
-                println!(
-                    "in {} manifest, updating {} from {cur_req} => {new_req}",
-                    dependant_name, pkg.name,
-                );
+        if dry_run {
+            println!("(dry run) not writing {changelog_path}");
+        } else {
+            fs::write(&changelog_path, promoted).unwrap();
+        }
+    }
+
+    // Worklist: every breaking change on a workspace member can force its dependents to widen
+    // their requirement, which is itself a breaking change for *them*. Walk the dependency graph
+    // outwards from the chosen package, collecting every member that needs a follow-on bump.
+    //
+    // `visited` guards the search itself against cycles (so a dependent's own dependents are only
+    // ever walked once). It's deliberately separate from wave membership: in a diamond -- two
+    // upstreams that both land in this wave and share a dependent -- that dependent must still
+    // collect a requirement update from *each* upstream, even though it only gets queued for
+    // further search once: the first time it's reached via a breaking edge.
+    let mut visited = HashSet::new();
+    visited.insert(pkg.id.clone());
+
+    let mut queue = VecDeque::new();
+    queue.push_back((pkg.id.clone(), pkg.version.clone(), new_version.clone()));
+
+    let mut wave: Vec<CascadeBump> = vec![];
+    let mut wave_index_by_id: HashMap<PackageId, usize> = HashMap::new();
+
+    while let Some((cur_id, cur_old_version, cur_new_version)) = queue.pop_front() {
+        let cur_pkg = &metadata[&cur_id];
+
+        // Only a real `Major` change in the upstream is itself a breaking change that has to
+        // cascade onto a dependent's own version. `updated_req` maps a `Prerelease` move (e.g. an
+        // exact pin tracking `1.0.0-beta.1` -> `1.0.0-beta.2`) to `UpdateReq` too, since the pin
+        // does need rewriting, but that's not breaking: the dependent must not be force-bumped
+        // (and its own fabricated "bump" must not cascade any further).
+        let cascade_is_breaking =
+            utils::bump_kind(&cur_old_version, &cur_new_version) == utils::BumpKind::Major;
 
-                let dependent_manifest_path = members
+        for (dependent, dep) in workspace_dependents_of(&metadata, &cur_pkg.name) {
+            let cur_req = &dep.req;
+
+            let new_req = match utils::updated_req(cur_req, &cur_old_version, &cur_new_version) {
+                utils::SemverUpdateKind::CurrentRequirementDoesNotMatchVersion => {
+                    eprintln!(
+                        "{}: requirement on {} ({cur_req}) does not match {cur_old_version}, so not touching it",
+                        dependent.name, cur_pkg.name,
+                    );
+                    continue;
+                }
+                utils::SemverUpdateKind::ExistingReqCompatible => {
+                    eprintln!(
+                        "{}: requirement on {} ({cur_req}) is compatible with {cur_new_version}, leaving it alone",
+                        dependent.name, cur_pkg.name,
+                    );
+                    continue;
+                }
+                utils::SemverUpdateKind::UpdateReq(req) => req,
+            };
+
+            let upstream_req = UpstreamReq {
+                upstream_name: cur_pkg.name.clone(),
+                dep: dep.clone(),
+                new_req,
+            };
+
+            if let Some(&idx) = wave_index_by_id.get(&dependent.id) {
+                // already in the wave via a different upstream edge -- record this requirement
+                // update too, rather than dropping it
+                wave[idx].reqs.push(upstream_req);
+
+                if cascade_is_breaking && wave[idx].level.is_none() {
+                    // a diamond: this dependent had so far only collected requirement-only
+                    // updates, but this upstream edge is a real breaking change, so it escalates
+                    // into a bump of its own and must cascade further too
+                    let bumped = wave[idx].old_version.increment_major();
+                    wave[idx].level = Some(utils::bump_kind(&wave[idx].old_version, &bumped));
+                    wave[idx].new_version = bumped.clone();
+
+                    queue.push_back((dependent.id.clone(), dependent.version.clone(), bumped));
+                }
+
+                continue;
+            }
+
+            if !visited.insert(dependent.id.clone()) {
+                // already visited without ever joining the wave; a cycle back to a package that
+                // isn't itself a dependent of anything in the wave
+                continue;
+            }
+
+            let (dependent_new_version, level) = if cascade_is_breaking {
+                let bumped = dependent.version.increment_major();
+                let level = utils::bump_kind(&dependent.version, &bumped);
+                (bumped, Some(level))
+            } else {
+                // requirement-only: the pin needs rewriting, but nothing breaking happened to
+                // this dependent's own version, so it keeps its current version and doesn't
+                // cascade any further
+                (dependent.version.clone(), None)
+            };
+
+            wave_index_by_id.insert(dependent.id.clone(), wave.len());
+            wave.push(CascadeBump {
+                name: dependent.name.clone(),
+                manifest_path: dependent.manifest_path.clone(),
+                old_version: dependent.version.clone(),
+                new_version: dependent_new_version.clone(),
+                level,
+                reqs: vec![upstream_req],
+            });
+
+            if cascade_is_breaking {
+                queue.push_back((dependent.id.clone(), dependent.version.clone(), dependent_new_version));
+            }
+        }
+    }
+
+    if !wave.is_empty() {
+        println!(
+            "{} workspace member(s) need a follow-on bump or requirement update because of changes cascading from {}.",
+            wave.len(),
+            pkg.name,
+        );
+
+        let items = wave
+            .iter()
+            .map(|bump| {
+                let reqs = bump
+                    .reqs
                     .iter()
-                    .find_map(|(pkg, _, _)| {
-                        (&pkg.name == dependant_name).then_some(pkg.manifest_path.clone())
+                    .map(|req| {
+                        let kind_label = dependency_kind_label(req.dep.kind)
+                            .map(|label| format!(" ({label})"))
+                            .unwrap_or_default();
+
+                        format!(
+                            "{}{kind_label} {} => {}",
+                            req.upstream_name, req.dep.req, req.new_req,
+                        )
                     })
-                    .unwrap();
+                    .join(", ");
+
+                let label = match &bump.level {
+                    Some(level) => format!(
+                        "[{level:?}] {} : {} => {} (requires {reqs})",
+                        bump.name, bump.old_version, bump.new_version,
+                    ),
+                    None => format!(
+                        "[requirement only] {} : {} (requires {reqs})",
+                        bump.name, bump.old_version,
+                    ),
+                };
+
+                (label, true)
+            })
+            .collect::<Vec<_>>();
+
+        let approved = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select the release wave to apply:")
+            .items_checked(&items)
+            .interact()
+            .unwrap();
+
+        // A bump's requirement updates assume the upstream package(s) named in `reqs` are
+        // actually being bumped. If an upstream is itself a wave entry the user unchecked, the
+        // requirement update would be writing a requirement on a version that was never
+        // released, so exclude the descendant too. Loop to a fixed point, since excluding one
+        // entry can in turn strand whatever depended on *it*.
+        let wave_index_by_name: HashMap<&str, usize> = wave
+            .iter()
+            .enumerate()
+            .map(|(i, bump)| (bump.name.as_str(), i))
+            .collect();
+
+        let mut approved: BTreeSet<usize> = approved.into_iter().collect();
+
+        loop {
+            let mut excluded = None;
 
-                let dependent_manifest = fs::read_to_string(&dependent_manifest_path).unwrap();
-                let mut dependent_manifest = dependent_manifest.parse::<Document>().unwrap();
+            for &i in &approved {
+                let bump = &wave[i];
 
-                let manifest_pkg_key = dep.rename.as_deref().unwrap_or(&dep.name);
+                let unmet_upstream = bump.reqs.iter().find(|req| {
+                    wave_index_by_name
+                        .get(req.upstream_name.as_str())
+                        .is_some_and(|upstream_idx| !approved.contains(upstream_idx))
+                });
 
-                utils::replace_toml_string(
-                    &mut dependent_manifest["dependencies"][manifest_pkg_key]["version"],
-                    utils::req_into_string(new_req),
+                if let Some(req) = unmet_upstream {
+                    println!(
+                        "excluding {} from the release wave: its requirement update on {} was approved, but the {} bump that would produce that version was unchecked",
+                        bump.name, req.upstream_name, req.upstream_name,
+                    );
+                    excluded = Some(i);
+                    break;
+                }
+            }
+
+            match excluded {
+                Some(i) => {
+                    approved.remove(&i);
+                }
+                None => break,
+            }
+        }
+
+        let workspace_root_manifest_path = metadata.workspace_root.join("Cargo.toml");
+        let mut root_edits = WorkspaceRootEdits::read(&workspace_root_manifest_path);
+
+        for index in approved {
+            let bump = &wave[index];
+
+            let dependent_manifest = fs::read_to_string(&bump.manifest_path).unwrap();
+            let mut dependent_manifest = dependent_manifest.parse::<Document>().unwrap();
+
+            if bump.level.is_some() {
+                utils::replace_toml_string_item(
+                    &mut dependent_manifest["package"]["version"],
+                    bump.new_version.to_string(),
+                );
+
+                println!(
+                    "in {} manifest, updating package version {} => {}",
+                    bump.manifest_path, bump.old_version, bump.new_version,
                 );
+            } else {
+                println!(
+                    "in {} manifest, leaving package version at {} (only its requirement(s) need rewriting)",
+                    bump.manifest_path, bump.old_version,
+                );
+            }
 
-                fs::write(&dependent_manifest_path, dependent_manifest.to_string()).unwrap();
+            // apply every requirement update onto the same in-memory document as the version
+            // bump above, so writing it once below carries both edits
+            for req in &bump.reqs {
+                update_dependency_requirement(
+                    &mut dependent_manifest,
+                    &bump.manifest_path,
+                    &req.dep,
+                    &req.new_req,
+                    &mut root_edits,
+                );
+            }
+
+            pending_manifest_edits.push((bump.manifest_path.clone(), dependent_manifest.to_string()));
+
+            if dry_run {
+                println!("(dry run) not writing {}", bump.manifest_path);
+            } else {
+                fs::write(&bump.manifest_path, dependent_manifest.to_string()).unwrap();
             }
         }
+
+        root_edits.flush(&mut pending_manifest_edits, dry_run);
+    }
+
+    if skip_clipboard {
+        println!("Skipping clipboard (--skip-clipboard)");
+    } else {
+        println!("Placing recommended commit message on clipboard");
+        arboard::Clipboard::new()
+            .unwrap()
+            .set_text(format!(
+                "chore({}): prepare release {new_version}",
+                pkg.name,
+            ))
+            .unwrap();
+    }
+
+    refresh_lockfile(&metadata, dry_run, &pending_manifest_edits);
+}
+
+/// Refreshes `Cargo.lock` for the manifest edits just made, and prints a summary of what changed.
+///
+/// In a dry run, nothing on the real workspace is touched; instead, `pending_manifest_edits` (the
+/// same manifest contents that would otherwise have been written) are materialized into a
+/// throwaway copy of the workspace, and `cargo update` is run there. Resolving against the
+/// unmodified real manifests would only ever surface unrelated registry drift, never the impact
+/// of the bump actually being previewed.
+fn refresh_lockfile(
+    metadata: &Metadata,
+    dry_run: bool,
+    pending_manifest_edits: &[(cargo_metadata::camino::Utf8PathBuf, String)],
+) {
+    let lockfile_path = metadata.workspace_root.join("Cargo.lock");
+
+    let before = fs::read_to_string(&lockfile_path)
+        .ok()
+        .as_deref()
+        .map(parse_lockfile_packages)
+        .unwrap_or_default();
+
+    if dry_run {
+        println!("(dry run) not writing {lockfile_path}");
+
+        let scratch_root = scratch_copy_workspace(metadata, pending_manifest_edits);
+
+        let status = std::process::Command::new("cargo")
+            .arg("update")
+            .arg("--workspace")
+            .arg("--manifest-path")
+            .arg(scratch_root.join("Cargo.toml"))
+            .status()
+            .expect("failed to run `cargo update` against the dry-run scratch copy");
+        assert!(
+            status.success(),
+            "`cargo update` did not succeed against the dry-run scratch copy"
+        );
+
+        let after =
+            parse_lockfile_packages(&fs::read_to_string(scratch_root.join("Cargo.lock")).unwrap());
+
+        fs::remove_dir_all(&scratch_root).ok();
+
+        print_lockfile_diff(&before, &after);
+
+        return;
+    }
+
+    let status = std::process::Command::new("cargo")
+        .arg("update")
+        .arg("--workspace")
+        .arg("--manifest-path")
+        .arg(metadata.workspace_root.join("Cargo.toml"))
+        .status()
+        .expect("failed to run `cargo update`");
+    assert!(status.success(), "`cargo update` did not succeed");
+
+    let after = parse_lockfile_packages(&fs::read_to_string(&lockfile_path).unwrap());
+
+    print_lockfile_diff(&before, &after);
+}
+
+/// Builds a throwaway copy of the workspace's manifests (and its existing lockfile, if any) under
+/// a temp directory, with `pending_manifest_edits` applied on top, so `cargo update` can be asked
+/// to resolve against manifests as they *would* look like after a dry run, without writing
+/// anything in the real workspace.
+fn scratch_copy_workspace(
+    metadata: &Metadata,
+    pending_manifest_edits: &[(cargo_metadata::camino::Utf8PathBuf, String)],
+) -> cargo_metadata::camino::Utf8PathBuf {
+    let scratch_root = cargo_metadata::camino::Utf8PathBuf::from_path_buf(
+        std::env::temp_dir().join(format!("bump-dry-run-{}", std::process::id())),
+    )
+    .expect("temp dir path was not valid UTF-8");
+
+    if scratch_root.exists() {
+        fs::remove_dir_all(&scratch_root).unwrap();
+    }
+
+    let workspace_manifest_path = metadata.workspace_root.join("Cargo.toml");
+    copy_into_scratch(&metadata.workspace_root, &scratch_root, &workspace_manifest_path);
+
+    for package in PkgIter(metadata, &metadata.workspace_members) {
+        copy_into_scratch(&metadata.workspace_root, &scratch_root, &package.manifest_path);
+    }
+
+    let lockfile_path = metadata.workspace_root.join("Cargo.lock");
+    if lockfile_path.exists() {
+        copy_into_scratch(&metadata.workspace_root, &scratch_root, &lockfile_path);
+    }
+
+    for (path, content) in pending_manifest_edits {
+        let dest = rebase(&metadata.workspace_root, &scratch_root, path);
+        fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        fs::write(dest, content).unwrap();
+    }
+
+    scratch_root
+}
+
+/// Copies a single file from under `from_root` to the same relative path under `to_root`.
+fn copy_into_scratch(
+    from_root: &cargo_metadata::camino::Utf8Path,
+    to_root: &cargo_metadata::camino::Utf8Path,
+    path: &cargo_metadata::camino::Utf8Path,
+) {
+    let dest = rebase(from_root, to_root, path);
+    fs::create_dir_all(dest.parent().unwrap()).unwrap();
+    fs::copy(path, dest).unwrap();
+}
+
+/// Rebases `path` (which must be under `from_root`) onto `to_root`.
+fn rebase(
+    from_root: &cargo_metadata::camino::Utf8Path,
+    to_root: &cargo_metadata::camino::Utf8Path,
+    path: &cargo_metadata::camino::Utf8Path,
+) -> cargo_metadata::camino::Utf8PathBuf {
+    to_root.join(path.strip_prefix(from_root).unwrap())
+}
+
+/// Parses a `Cargo.lock` into `(name, version)` pairs for each locked package. A name can appear
+/// more than once at different versions -- semver-incompatible duplicates are routine in real
+/// dependency graphs -- so callers must diff on the pair, not the name alone.
+fn parse_lockfile_packages(lockfile: &str) -> Vec<(String, String)> {
+    let doc = lockfile.parse::<Document>().unwrap();
+
+    let Some(packages) = doc.get("package").and_then(toml_edit::Item::as_array_of_tables) else {
+        return vec![];
     };
 
-    println!("Placing recommended commit message on clipboard");
-    arboard::Clipboard::new()
-        .unwrap()
-        .set_text(format!(
-            "chore({}): prepare release {new_version}",
-            pkg.name,
-        ))
-        .unwrap();
+    packages
+        .iter()
+        .filter_map(|pkg| {
+            let name = pkg.get("name")?.as_str()?.to_owned();
+            let version = pkg.get("version")?.as_str()?.to_owned();
+            Some((name, version))
+        })
+        .collect()
+}
+
+/// Diffs two `Cargo.lock` package listings and prints a colored, cargo-`print_lockfile_update`
+/// style summary of what was added, removed, or updated.
+fn print_lockfile_diff(before: &[(String, String)], after: &[(String, String)]) {
+    let lines = lockfile_diff_lines(before, after);
+
+    if lines.is_empty() {
+        println!("Cargo.lock is unchanged");
+    } else {
+        for line in lines {
+            println!("{line}");
+        }
+    }
+}
+
+/// Pure implementation of [`print_lockfile_diff`]'s diffing, returning the formatted lines instead
+/// of printing them directly, so it's testable without capturing stdout. Empty means unchanged.
+///
+/// Diffs per-name sets of versions rather than collapsing each name to a single version: a crate
+/// can have multiple `[[package]]` entries at different, semver-incompatible versions, and each
+/// must be tracked independently or a real change to one is silently lost next to an unchanged
+/// other.
+fn lockfile_diff_lines(before: &[(String, String)], after: &[(String, String)]) -> Vec<String> {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let mut before_versions: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (name, version) in before {
+        before_versions.entry(name).or_default().insert(version);
+    }
+
+    let mut after_versions: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (name, version) in after {
+        after_versions.entry(name).or_default().insert(version);
+    }
+
+    let names = before_versions.keys().chain(after_versions.keys()).collect::<BTreeSet<_>>();
+
+    let mut lines = vec![];
+
+    for &name in names {
+        let before = before_versions.get(name).cloned().unwrap_or_default();
+        let after = after_versions.get(name).cloned().unwrap_or_default();
+
+        let mut added = after.difference(&before).copied().collect::<Vec<_>>();
+        let mut removed = before.difference(&after).copied().collect::<Vec<_>>();
+
+        if let ([new_version], [old_version]) = (added.as_slice(), removed.as_slice()) {
+            lines.push(format!("\x1b[33mUpdating\x1b[0m {name} v{old_version} -> v{new_version}"));
+            continue;
+        }
+
+        added.sort_unstable();
+        removed.sort_unstable();
+
+        for new_version in added {
+            lines.push(format!("\x1b[32m Adding\x1b[0m {name} v{new_version}"));
+        }
+
+        for old_version in removed {
+            lines.push(format!("\x1b[31mRemoving\x1b[0m {name} v{old_version}"));
+        }
+    }
+
+    lines
+}
+
+/// A planned change to a workspace member -- a version bump, or only requirement update(s) --
+/// cascaded from a bump of one or more of its dependencies.
+struct CascadeBump {
+    name: String,
+    manifest_path: cargo_metadata::camino::Utf8PathBuf,
+    old_version: semver::Version,
+    new_version: semver::Version,
+
+    /// `None` when nothing breaking happened to this member's own version -- every upstream
+    /// trigger it collected was itself non-breaking (e.g. a `Prerelease` move on an exactly
+    /// pinned dependency) -- so `old_version == new_version` and only its requirement(s) need
+    /// rewriting.
+    level: Option<utils::BumpKind>,
+
+    /// The requirement update(s) this bump needs to make on its own manifest. Usually one entry,
+    /// but a member depending on two different packages that are both bumped in the same wave
+    /// (a diamond) collects one entry per upstream.
+    reqs: Vec<UpstreamReq>,
+}
+
+/// A single dependency requirement that needs rewriting because the upstream package named by it
+/// was bumped in this release wave (a breaking bump, or a pre-release move on an exact pin).
+struct UpstreamReq {
+    upstream_name: String,
+    dep: Dependency,
+    new_req: semver::VersionReq,
+}
+
+/// Workspace members that have `name` as a dependency (of any kind: normal, dev, or build),
+/// paired with the edge that names it.
+fn workspace_dependents_of<'a>(
+    metadata: &'a Metadata,
+    name: &str,
+) -> Vec<(&'a Package, Dependency)> {
+    PkgIter(metadata, &metadata.workspace_members)
+        .filter_map(|pkg| {
+            pkg.dependencies
+                .iter()
+                .find_map(|dep| (dep.name == name).then_some((pkg, dep.clone())))
+        })
+        .collect()
+}
+
+/// A short label for a non-normal dependency kind, for display purposes. `None` for
+/// `DependencyKind::Normal`, which is the common case and needs no callout.
+fn dependency_kind_label(kind: DependencyKind) -> Option<&'static str> {
+    match kind {
+        DependencyKind::Normal => None,
+        DependencyKind::Development => Some("dev"),
+        DependencyKind::Build => Some("build"),
+        DependencyKind::Unknown => None,
+    }
+}
+
+/// The table path (e.g. `["dependencies"]`, or `["target", "cfg(unix)", "build-dependencies"]`)
+/// that a dependency edge's entry lives under in its manifest.
+fn dependency_table_path(dep: &Dependency) -> Vec<String> {
+    let kind_table = match dep.kind {
+        DependencyKind::Development => "dev-dependencies",
+        DependencyKind::Build => "build-dependencies",
+        DependencyKind::Normal | DependencyKind::Unknown => "dependencies",
+    };
+
+    match &dep.target {
+        Some(platform) => vec![
+            "target".to_owned(),
+            platform.to_string(),
+            kind_table.to_owned(),
+        ],
+        None => vec![kind_table.to_owned()],
+    }
+}
+
+/// Navigates a parsed manifest down a table path (see [`dependency_table_path`]).
+fn locate_table<'a>(manifest: &'a mut Document, path: &[String]) -> &'a mut toml_edit::Item {
+    let mut item = &mut manifest[path[0].as_str()];
+
+    for segment in &path[1..] {
+        item = &mut item[segment.as_str()];
+    }
+
+    item
+}
+
+/// Whether a dependency entry (e.g. `foo = { workspace = true }`) inherits its requirement from
+/// the workspace root's `[workspace.dependencies]` table, rather than declaring its own.
+fn is_workspace_inherited(dep_entry: &toml_edit::Item) -> bool {
+    dep_entry
+        .as_table_like()
+        .and_then(|table| table.get("workspace"))
+        .and_then(toml_edit::Item::as_bool)
+        .unwrap_or(false)
+}
+
+struct WorkspaceRootEdits<'a> {
+    workspace_root_manifest_path: &'a cargo_metadata::camino::Utf8PathBuf,
+    manifest: Document,
+    /// Whether `manifest` has any edits pending a write; the root manifest may not need touching
+    /// at all if nothing in the wave inherits from `[workspace.dependencies]`.
+    touched: bool,
+    /// Workspace-inherited dependency keys already redirected to the root manifest, so a
+    /// dependency shared by several members is only edited there once.
+    written: HashSet<String>,
+}
+
+impl<'a> WorkspaceRootEdits<'a> {
+    fn read(workspace_root_manifest_path: &'a cargo_metadata::camino::Utf8PathBuf) -> Self {
+        let manifest = fs::read_to_string(workspace_root_manifest_path)
+            .unwrap()
+            .parse::<Document>()
+            .unwrap();
+
+        WorkspaceRootEdits {
+            workspace_root_manifest_path,
+            manifest,
+            touched: false,
+            written: HashSet::new(),
+        }
+    }
+
+    /// Writes `manifest` back out (or records it into `pending_manifest_edits` for a dry run), if
+    /// anything actually touched it.
+    fn flush(
+        self,
+        pending_manifest_edits: &mut Vec<(cargo_metadata::camino::Utf8PathBuf, String)>,
+        dry_run: bool,
+    ) {
+        if !self.touched {
+            return;
+        }
+
+        pending_manifest_edits.push((
+            self.workspace_root_manifest_path.clone(),
+            self.manifest.to_string(),
+        ));
+
+        if dry_run {
+            println!("(dry run) not writing {}", self.workspace_root_manifest_path);
+        } else {
+            fs::write(self.workspace_root_manifest_path, self.manifest.to_string()).unwrap();
+        }
+    }
+}
+
+/// Updates a dependency's version requirement to `new_req`, in whichever manifest actually owns
+/// it: `dependent_manifest` itself (accounting for dependency kind and target-specific tables),
+/// or -- if the dependency is inherited via `workspace = true` -- the workspace root's
+/// `[workspace.dependencies]` table, which is only written once no matter how many members
+/// inherit from it.
+///
+/// Edits `dependent_manifest` in place rather than re-reading it from disk, so that it composes
+/// with other in-flight edits (e.g. the dependent's own version bump) the caller has already made
+/// to the same document; the caller is responsible for writing it out. The workspace root
+/// manifest is edited the same way, in `root.manifest`: if it were instead re-read from disk on
+/// every call, a wave needing requirement updates on two different workspace-inherited
+/// dependencies would have its second call clobber the first's edit once both were applied to the
+/// real file.
+fn update_dependency_requirement(
+    dependent_manifest: &mut Document,
+    dependent_manifest_path: &cargo_metadata::camino::Utf8PathBuf,
+    dep: &Dependency,
+    new_req: &semver::VersionReq,
+    root: &mut WorkspaceRootEdits<'_>,
+) {
+    let manifest_key = dep.rename.clone().unwrap_or_else(|| dep.name.clone());
+    let table_path = dependency_table_path(dep);
+
+    let table = locate_table(dependent_manifest, &table_path);
+
+    if is_workspace_inherited(&table[manifest_key.as_str()]) {
+        if !root.written.insert(manifest_key.clone()) {
+            println!(
+                "workspace dependency {manifest_key} was already updated via another member, leaving {dependent_manifest_path} alone"
+            );
+            return;
+        }
+
+        println!(
+            "in {} manifest, updating workspace.dependencies.{manifest_key} requirement to {new_req}",
+            root.workspace_root_manifest_path,
+        );
+
+        utils::replace_toml_string_item(
+            &mut root.manifest["workspace"]["dependencies"][manifest_key.as_str()]["version"],
+            utils::req_into_string(new_req),
+        );
+        root.touched = true;
+
+        return;
+    }
+
+    println!(
+        "in {dependent_manifest_path} manifest, updating {manifest_key} requirement to {new_req}"
+    );
+
+    utils::replace_toml_string_item(
+        &mut table[manifest_key.as_str()]["version"],
+        utils::req_into_string(new_req),
+    );
 }
 
 /// Iterate over packages given their package IDs.
@@ -203,6 +819,100 @@ impl<'a> Iterator for PkgIter<'a> {
     }
 }
 
+/// Prompts the user to pick a bump level and returns the confirmed new version as a string.
+///
+/// `Auto` derives a level from `unreleased`, preferring `Major` when the changelog section
+/// mentions a breaking change and falling back to `Minor` otherwise. `Alpha`/`Beta`/`Rc` move (or
+/// advance within) that pre-release phase; picking one that would move backwards relative to
+/// `pkg.version`'s current phase falls back to free-text entry, same as declining a preview.
+/// `Custom` (and declining the preview of a computed level) falls back to free-text entry via
+/// [`prompt_custom_version`].
+fn select_new_version(pkg: &Package, unreleased: Option<&str>) -> String {
+    let levels = [
+        "Auto", "Patch", "Minor", "Major", "Alpha", "Beta", "Rc", "Custom",
+    ];
+
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Bump level:")
+        .items(&levels)
+        .default(0)
+        .interact()
+        .unwrap();
+
+    let candidate = match levels[selection] {
+        "Auto" => {
+            if unreleased.is_some_and(has_breaking_marker) {
+                pkg.version.increment_major()
+            } else {
+                pkg.version.increment_minor()
+            }
+        }
+        "Patch" => pkg.version.increment_patch(),
+        "Minor" => pkg.version.increment_minor(),
+        "Major" => pkg.version.increment_major(),
+        "Alpha" => match pkg.version.increment_alpha() {
+            Ok(candidate) => candidate,
+            Err(err) => {
+                eprintln!("{err}");
+                return prompt_custom_version(pkg);
+            }
+        },
+        "Beta" => match pkg.version.increment_beta() {
+            Ok(candidate) => candidate,
+            Err(err) => {
+                eprintln!("{err}");
+                return prompt_custom_version(pkg);
+            }
+        },
+        "Rc" => match pkg.version.increment_rc() {
+            Ok(candidate) => candidate,
+            Err(err) => {
+                eprintln!("{err}");
+                return prompt_custom_version(pkg);
+            }
+        },
+        _ => return prompt_custom_version(pkg),
+    };
+
+    if candidate <= pkg.version {
+        // e.g. `Alpha` on a release `1.0.0` gives `1.0.0-alpha.1`, which sorts below it
+        eprintln!("{candidate} is not higher than {}", pkg.version);
+        return prompt_custom_version(pkg);
+    }
+
+    println!("{} → {candidate}", pkg.version);
+
+    let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Use this version?")
+        .default(true)
+        .interact()
+        .unwrap();
+
+    if confirmed {
+        candidate.to_string()
+    } else {
+        prompt_custom_version(pkg)
+    }
+}
+
+fn prompt_custom_version(pkg: &Package) -> String {
+    Input::<String>::with_theme(&ColorfulTheme::default())
+        .with_prompt("New version:")
+        .validate_with(|input: &String| -> Result<(), String> {
+            let Ok(v2) = semver::Version::parse(input) else {
+                return Err(format!("{input} is not a valid SemVer string"));
+            };
+
+            if v2 <= pkg.version {
+                return Err("New version must be higher than current version".to_owned());
+            }
+
+            Ok(())
+        })
+        .interact_text()
+        .unwrap()
+}
+
 fn member_prompt(
     name: &str,
     version: &semver::Version,
@@ -258,32 +968,370 @@ fn member_prompt(
 }
 
 trait Changelog {
+    fn changelog_path(&self) -> Option<cargo_metadata::camino::Utf8PathBuf>;
+
     fn read_changelog(&self) -> Option<String>;
 
     fn extract_unreleased(&self) -> Option<String>;
+
+    /// Rewrites the changelog following the Keep a Changelog convention: a new
+    /// `## [x.y.z] - YYYY-MM-DD` heading is inserted immediately after the `## [Unreleased]`
+    /// heading, taking the Unreleased section's entries with it and leaving Unreleased empty for
+    /// future entries. Returns `None` if there's no changelog, or no `Unreleased` heading to
+    /// promote.
+    fn promote_unreleased(&self, new_version: &semver::Version, date: &str) -> Option<String>;
 }
 
 impl Changelog for Package {
-    fn read_changelog(&self) -> Option<String> {
+    fn changelog_path(&self) -> Option<cargo_metadata::camino::Utf8PathBuf> {
         [
             self.manifest_path.with_file_name("CHANGELOG.md"),
             self.manifest_path.with_file_name("RELEASES.md"),
             self.manifest_path.with_file_name("CHANGES.md"),
         ]
         .into_iter()
-        .find_map(|path| fs::read_to_string(path).ok())
+        .find(|path| path.exists())
+    }
+
+    fn read_changelog(&self) -> Option<String> {
+        fs::read_to_string(self.changelog_path()?).ok()
     }
 
     fn extract_unreleased(&self) -> Option<String> {
         let changelog = self.read_changelog()?;
+        let lines = changelog.lines().collect::<Vec<_>>();
+
+        let (level, heading_idx) = find_heading(&lines, "Unreleased")?;
+        let section_end = find_section_end(&lines, heading_idx, level);
+
+        let unreleased = lines[heading_idx + 1..section_end].iter().join("\n");
+
+        Some(unreleased.trim().to_owned())
+    }
+
+    fn promote_unreleased(&self, new_version: &semver::Version, date: &str) -> Option<String> {
+        promote_unreleased_text(&self.read_changelog()?, new_version, date)
+    }
+}
+
+/// Pure implementation of [`Changelog::promote_unreleased`], operating directly on changelog text
+/// rather than reading it from disk, so it's testable without a real `Package`.
+fn promote_unreleased_text(
+    changelog: &str,
+    new_version: &semver::Version,
+    date: &str,
+) -> Option<String> {
+    let lines = changelog.lines().collect::<Vec<_>>();
+
+    let (level, heading_idx) = find_heading(&lines, "Unreleased")?;
+    let section_end = find_section_end(&lines, heading_idx, level);
+
+    let entries = lines[heading_idx + 1..section_end]
+        .iter()
+        .copied()
+        .skip_while(|line| line.trim().is_empty())
+        .collect::<Vec<_>>();
+    let entries = trim_trailing_blank(&entries);
+
+    let heading_level = "#".repeat(level);
+
+    let mut out = lines[..=heading_idx]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+
+    out.push(String::new());
+    out.push(format!("{heading_level} [{new_version}] - {date}"));
+
+    if !entries.is_empty() {
+        out.push(String::new());
+        out.extend(entries.iter().map(|line| line.to_string()));
+    }
+
+    out.push(String::new());
+    out.extend(lines[section_end..].iter().map(|line| line.to_string()));
+
+    let promoted = out.join("\n");
+
+    Some(update_comparison_links(&promoted, new_version).unwrap_or(promoted))
+}
+
+/// Whether an Unreleased changelog section flags a breaking change: either a line starting with
+/// "Breaking" (e.g. a `- Breaking: ...` entry), or a `### Breaking ...` subheading. Deliberately
+/// narrower than a bare substring match, which would also fire on prose like "non-breaking
+/// refactor" or "no breaking changes".
+fn has_breaking_marker(notes: &str) -> bool {
+    notes.lines().any(|line| {
+        let line = line.trim().trim_start_matches(['-', '*']).trim();
+
+        match heading_level(line) {
+            Some((_, heading)) => heading.to_lowercase().starts_with("breaking"),
+            None => line.to_lowercase().starts_with("breaking"),
+        }
+    })
+}
+
+/// Finds a Markdown heading whose text (ignoring leading `#`s and surrounding `[...]`) matches
+/// `title`, case-insensitively. Returns its heading level (number of `#`s) and line index.
+fn find_heading(lines: &[&str], title: &str) -> Option<(usize, usize)> {
+    lines.iter().enumerate().find_map(|(i, line)| {
+        let (level, heading) = heading_level(line)?;
+
+        heading
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .eq_ignore_ascii_case(title)
+            .then_some((level, i))
+    })
+}
+
+/// Returns the number of leading `#`s and the trimmed heading text, or `None` if `line` isn't a
+/// Markdown heading.
+fn heading_level(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+
+    (level > 0).then(|| (level, line[level..].trim()))
+}
+
+/// Finds the index of the next heading at or above `level` after `heading_idx`, or `lines.len()`
+/// if the section runs to the end of the file.
+fn find_section_end(lines: &[&str], heading_idx: usize, level: usize) -> usize {
+    lines[heading_idx + 1..]
+        .iter()
+        .position(|line| heading_level(line).is_some_and(|(l, _)| l <= level))
+        .map_or(lines.len(), |pos| heading_idx + 1 + pos)
+}
+
+/// Trims trailing blank lines from a slice of lines.
+fn trim_trailing_blank<'a>(lines: &'a [&'a str]) -> &'a [&'a str] {
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |pos| pos + 1);
+
+    &lines[..end]
+}
+
+/// Updates the Keep a Changelog reference-style `[Unreleased]: .../compare/<tag>...HEAD` link (if
+/// present) to start from the newly released tag, and inserts a comparison link for the new
+/// version using the previous tag as its start.
+fn update_comparison_links(changelog: &str, new_version: &semver::Version) -> Option<String> {
+    let unreleased_prefix = "[Unreleased]: ";
+
+    let mut lines = changelog.lines().map(str::to_owned).collect::<Vec<_>>();
+
+    let idx = lines.iter().position(|line| line.starts_with(unreleased_prefix))?;
+
+    let url = lines[idx].trim_start_matches(unreleased_prefix);
+    let (base, range) = url.rsplit_once("/compare/")?;
+    let (prev_tag, _head) = range.split_once("...")?;
+
+    let base = base.to_owned();
+    let prev_tag = prev_tag.to_owned();
+    let new_tag = format!("v{new_version}");
+
+    lines[idx] = format!("{unreleased_prefix}{base}/compare/{new_tag}...HEAD");
+    lines.insert(
+        idx + 1,
+        format!("[{new_version}]: {base}/compare/{prev_tag}...{new_tag}"),
+    );
+
+    Some(lines.join("\n"))
+}
+
+/// Shells out to `date` for today's date, since the tool doesn't otherwise depend on a datetime
+/// crate.
+fn today() -> String {
+    let output = std::process::Command::new("date")
+        .arg("+%Y-%m-%d")
+        .output()
+        .expect("failed to run `date`");
+
+    String::from_utf8(output.stdout)
+        .expect("`date` output was not valid UTF-8")
+        .trim()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! v {
+        ($ver:literal) => {
+            $ver.parse::<::semver::Version>().unwrap()
+        };
+    }
+
+    #[test]
+    fn heading_level_parses_level_and_text() {
+        assert_eq!(heading_level("## [Unreleased]"), Some((2, "[Unreleased]")));
+        assert_eq!(heading_level("# Title"), Some((1, "Title")));
+        assert_eq!(heading_level("not a heading"), None);
+    }
+
+    #[test]
+    fn find_heading_matches_case_insensitively_and_ignores_brackets() {
+        let lines = ["# Changelog", "", "## [Unreleased]", "", "## [1.0.0] - 2024-01-01"];
+
+        assert_eq!(find_heading(&lines, "unreleased"), Some((2, 2)));
+        assert_eq!(find_heading(&lines, "nonexistent"), None);
+    }
+
+    #[test]
+    fn find_section_end_stops_at_next_heading_of_same_or_higher_level() {
+        let lines = [
+            "## [Unreleased]",
+            "- entry one",
+            "### Subsection",
+            "- nested entry",
+            "## [1.0.0] - 2024-01-01",
+        ];
+
+        // a nested `###` subsection doesn't end the `##` section
+        assert_eq!(find_section_end(&lines, 0, 2), 4);
+    }
+
+    #[test]
+    fn find_section_end_runs_to_end_of_file_if_no_following_heading() {
+        let lines = ["## [Unreleased]", "- entry one"];
+
+        assert_eq!(find_section_end(&lines, 0, 2), 2);
+    }
+
+    #[test]
+    fn trim_trailing_blank_drops_trailing_empty_lines_only() {
+        let lines = ["- entry one", "", "- entry two", "", ""];
+
+        assert_eq!(trim_trailing_blank(&lines), &["- entry one", "", "- entry two"]);
+    }
+
+    #[test]
+    fn trim_trailing_blank_all_blank_collapses_to_empty() {
+        let lines = ["", ""];
+
+        assert_eq!(trim_trailing_blank(&lines), &[] as &[&str]);
+    }
+
+    #[test]
+    fn has_breaking_marker_requires_a_specific_signal() {
+        assert!(!has_breaking_marker("non-breaking refactor"));
+        assert!(!has_breaking_marker("no breaking changes"));
+        assert!(has_breaking_marker("- Breaking: removed Foo::bar"));
+        assert!(has_breaking_marker("### Breaking Changes\n- removed Foo::bar"));
+    }
+
+    #[test]
+    fn update_comparison_links_rewrites_unreleased_and_inserts_new_version_link() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n\n- something\n\n[Unreleased]: https://example.com/repo/compare/v1.0.0...HEAD\n";
+
+        let updated = update_comparison_links(changelog, &v!("1.1.0")).unwrap();
+
+        assert!(updated.contains("[Unreleased]: https://example.com/repo/compare/v1.1.0...HEAD"));
+        assert!(updated.contains("[1.1.0]: https://example.com/repo/compare/v1.0.0...v1.1.0"));
+    }
+
+    #[test]
+    fn update_comparison_links_no_reference_link_returns_none() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n\n- something\n";
+
+        assert_eq!(update_comparison_links(changelog, &v!("1.1.0")), None);
+    }
+
+    #[test]
+    fn promote_unreleased_text_moves_entries_under_a_new_heading() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n\n- added a thing\n\n## [1.0.0] - 2024-01-01\n\n- initial release\n";
+
+        let promoted = promote_unreleased_text(changelog, &v!("1.1.0"), "2024-06-01").unwrap();
+
+        assert!(promoted.contains("## [1.1.0] - 2024-06-01"));
+
+        let unreleased_idx = promoted.find("## [Unreleased]").unwrap();
+        let new_version_idx = promoted.find("## [1.1.0]").unwrap();
+        assert!(unreleased_idx < new_version_idx);
+        // the entry moved out from under [Unreleased] rather than lingering in both places
+        assert!(!promoted[unreleased_idx..new_version_idx].contains("added a thing"));
+        assert!(promoted[new_version_idx..].contains("added a thing"));
+    }
+
+    #[test]
+    fn promote_unreleased_text_no_unreleased_heading_returns_none() {
+        let changelog = "# Changelog\n\n## [1.0.0] - 2024-01-01\n";
+
+        assert_eq!(promote_unreleased_text(changelog, &v!("1.1.0"), "2024-06-01"), None);
+    }
+
+    #[test]
+    fn parse_lockfile_packages_extracts_name_version_pairs_including_duplicate_names() {
+        let lockfile = r#"
+version = 3
+
+[[package]]
+name = "foo"
+version = "1.0.0"
+
+[[package]]
+name = "foo"
+version = "2.0.0"
+
+[[package]]
+name = "bar"
+version = "0.1.0"
+"#;
+
+        assert_eq!(
+            parse_lockfile_packages(lockfile),
+            vec![
+                ("foo".to_owned(), "1.0.0".to_owned()),
+                ("foo".to_owned(), "2.0.0".to_owned()),
+                ("bar".to_owned(), "0.1.0".to_owned()),
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_lockfile_packages_missing_package_array_returns_empty() {
+        assert_eq!(parse_lockfile_packages("version = 3\n"), vec![]);
+    }
+
+    #[test]
+    fn lockfile_diff_distinguishes_same_name_different_version_entries() {
+        // one `foo` entry changed, the other (at a different, unrelated version) didn't -- this
+        // must surface as a real update, not be silently collapsed into "unchanged"
+        let before = vec![
+            ("foo".to_owned(), "1.0.0".to_owned()),
+            ("foo".to_owned(), "2.0.0".to_owned()),
+        ];
+        let after = vec![
+            ("foo".to_owned(), "1.1.0".to_owned()),
+            ("foo".to_owned(), "2.0.0".to_owned()),
+        ];
+
+        assert_eq!(
+            lockfile_diff_lines(&before, &after),
+            vec!["\x1b[33mUpdating\x1b[0m foo v1.0.0 -> v1.1.0".to_owned()],
+        );
+    }
+
+    #[test]
+    fn lockfile_diff_reports_additions_and_removals_when_not_a_clean_swap() {
+        let before = vec![("foo".to_owned(), "1.0.0".to_owned())];
+        let after = vec![
+            ("foo".to_owned(), "1.0.0".to_owned()),
+            ("foo".to_owned(), "2.0.0".to_owned()),
+        ];
+
+        assert_eq!(
+            lockfile_diff_lines(&before, &after),
+            vec!["\x1b[32m Adding\x1b[0m foo v2.0.0".to_owned()],
+        );
+    }
 
-        let unreleased = changelog
-            .lines()
-            .skip_while(|line| !line.ends_with("Unreleased"))
-            .skip(1)
-            .take_while(|line| !line.ends_with(&self.version.to_string()))
-            .join("\n");
+    #[test]
+    fn lockfile_diff_no_changes_is_empty() {
+        let before = vec![("foo".to_owned(), "1.0.0".to_owned())];
+        let after = before.clone();
 
-        Some(unreleased)
+        assert!(lockfile_diff_lines(&before, &after).is_empty());
     }
 }
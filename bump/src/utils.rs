@@ -10,11 +10,130 @@ pub(crate) fn replace_toml_string_value(item: &mut toml_edit::Value, new_val: im
     *item.decor_mut() = decor;
 }
 
+/// Extension methods for constructing the next version in a given bump level.
+///
+/// Mirrors the `BumpSpec` idea from `cargo-smart-release`: `increment_major`/`increment_minor`/
+/// `increment_patch` clear any pre-release identifiers on the returned version, since a
+/// `Patch`/`Minor`/`Major` bump always lands on a release version. The `alpha`/`beta`/`rc`
+/// methods instead keep major/minor/patch and set a pre-release tag.
+pub(crate) trait VersionExt {
+    fn increment_major(&self) -> semver::Version;
+    fn increment_minor(&self) -> semver::Version;
+    fn increment_patch(&self) -> semver::Version;
+
+    /// Moves (or advances within) the `alpha` pre-release phase.
+    ///
+    /// Errors if `self` is already in a later phase (`beta` or `rc`), since that would be a
+    /// backwards move.
+    fn increment_alpha(&self) -> Result<semver::Version, String>;
+
+    /// Moves (or advances within) the `beta` pre-release phase.
+    ///
+    /// Errors if `self` is already in a later phase (`rc`), since that would be a backwards move.
+    fn increment_beta(&self) -> Result<semver::Version, String>;
+
+    /// Moves (or advances within) the `rc` pre-release phase.
+    fn increment_rc(&self) -> Result<semver::Version, String>;
+}
+
+impl VersionExt for semver::Version {
+    fn increment_major(&self) -> semver::Version {
+        semver::Version::new(self.major + 1, 0, 0)
+    }
+
+    fn increment_minor(&self) -> semver::Version {
+        semver::Version::new(self.major, self.minor + 1, 0)
+    }
+
+    fn increment_patch(&self) -> semver::Version {
+        semver::Version::new(self.major, self.minor, self.patch + 1)
+    }
+
+    fn increment_alpha(&self) -> Result<semver::Version, String> {
+        self.bump_prerelease_phase(PrereleasePhase::Alpha)
+    }
+
+    fn increment_beta(&self) -> Result<semver::Version, String> {
+        self.bump_prerelease_phase(PrereleasePhase::Beta)
+    }
+
+    fn increment_rc(&self) -> Result<semver::Version, String> {
+        self.bump_prerelease_phase(PrereleasePhase::Rc)
+    }
+}
+
+/// Ordering of pre-release phases: `alpha < beta < rc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum PrereleasePhase {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl fmt::Display for PrereleasePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PrereleasePhase::Alpha => "alpha",
+            PrereleasePhase::Beta => "beta",
+            PrereleasePhase::Rc => "rc",
+        })
+    }
+}
+
+/// Parses a `semver::Prerelease` of the form `<phase>` or `<phase>.<n>` into its phase and
+/// numeric suffix (defaulting the suffix to `0` when absent).
+fn parse_prerelease_phase(pre: &semver::Prerelease) -> Option<(PrereleasePhase, u64)> {
+    if pre.is_empty() {
+        return None;
+    }
+
+    let mut parts = pre.as_str().splitn(2, '.');
+
+    let phase = match parts.next()? {
+        "alpha" => PrereleasePhase::Alpha,
+        "beta" => PrereleasePhase::Beta,
+        "rc" => PrereleasePhase::Rc,
+        _ => return None,
+    };
+
+    let n = parts.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+
+    Some((phase, n))
+}
+
+trait BumpPrereleasePhase {
+    fn bump_prerelease_phase(&self, phase: PrereleasePhase) -> Result<semver::Version, String>;
+}
+
+impl BumpPrereleasePhase for semver::Version {
+    fn bump_prerelease_phase(&self, phase: PrereleasePhase) -> Result<semver::Version, String> {
+        let n = match parse_prerelease_phase(&self.pre) {
+            Some((cur_phase, _)) if cur_phase > phase => {
+                return Err(format!("cannot move from {cur_phase} back to {phase}"));
+            }
+            Some((cur_phase, n)) if cur_phase == phase => n + 1,
+            _ => 1,
+        };
+
+        Ok(semver::Version {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            pre: semver::Prerelease::new(&format!("{phase}.{n}")).unwrap(),
+            build: semver::BuildMetadata::EMPTY,
+        })
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum BumpKind {
     Patch,
     Minor,
     Major,
+
+    /// Same major/minor/patch as the current version, but a different pre-release tag (e.g.
+    /// `1.0.0-beta.1` -> `1.0.0-beta.2`, or `1.0.0-rc.1` -> `1.0.0`).
+    Prerelease,
 }
 
 pub(crate) fn bump_kind(cur: &semver::Version, new: &semver::Version) -> BumpKind {
@@ -38,6 +157,11 @@ pub(crate) fn bump_kind(cur: &semver::Version, new: &semver::Version) -> BumpKin
             return BumpKind::Major;
         }
 
+        if new.patch == cur.patch && cur.pre != new.pre {
+            // eg: 0.1.0-beta.1 -> 0.1.0-beta.2, or 0.1.0-rc.1 -> 0.1.0
+            return BumpKind::Prerelease;
+        }
+
         // 0.x.y -> 0.x.z changes should always be treated as minor
         return BumpKind::Minor;
     }
@@ -59,7 +183,12 @@ pub(crate) fn bump_kind(cur: &semver::Version, new: &semver::Version) -> BumpKin
         return BumpKind::Patch;
     }
 
-    unimplemented!("beta versions are not considered")
+    if cur.pre != new.pre {
+        // eg: 1.0.0-beta.1 -> 1.0.0-beta.2, or 1.0.0-rc.1 -> 1.0.0
+        return BumpKind::Prerelease;
+    }
+
+    unimplemented!("versions are identical other than build metadata")
 }
 
 #[derive(Debug, PartialEq)]
@@ -92,10 +221,17 @@ pub(crate) fn updated_req(
         BumpKind::Patch => SemverUpdateKind::ExistingReqCompatible,
         BumpKind::Minor => SemverUpdateKind::ExistingReqCompatible,
         BumpKind::Major => SemverUpdateKind::UpdateReq(to_min_req(v2)),
+        BumpKind::Prerelease => SemverUpdateKind::UpdateReq(to_min_req(v2)),
     }
 }
 
 pub(crate) fn to_min_req(ver: &semver::Version) -> semver::VersionReq {
+    if !ver.pre.is_empty() {
+        // caret/tilde requirements never match pre-release versions, so the only requirement
+        // that can track one is an exact pin, e.g. `=1.0.0-beta.1`
+        return semver::VersionReq::parse(&format!("={ver}")).unwrap();
+    }
+
     let ver = ver.to_string();
     semver::VersionReq::parse(ver.trim_end_matches(".0")).unwrap()
 }
@@ -183,6 +319,18 @@ mod tests {
         };
     }
 
+    #[test]
+    fn version_increments() {
+        assert_eq!(v!("1.2.3").increment_patch(), v!("1.2.4"));
+        assert_eq!(v!("1.2.3").increment_minor(), v!("1.3.0"));
+        assert_eq!(v!("1.2.3").increment_major(), v!("2.0.0"));
+
+        // pre-release identifiers are cleared
+        assert_eq!(v!("1.2.3-beta.1").increment_patch(), v!("1.2.4"));
+        assert_eq!(v!("1.2.3-beta.1").increment_minor(), v!("1.3.0"));
+        assert_eq!(v!("1.2.3-beta.1").increment_major(), v!("2.0.0"));
+    }
+
     #[test]
     fn version_to_min_req() {
         assert_eq!(to_min_req(&v!("0.0.1")), req!("0.0.1"));
@@ -212,6 +360,50 @@ mod tests {
         assert_eq!(bump_kind(&v!("1.0.0"), &v!("1.0.1")), BumpKind::Patch);
         assert_eq!(bump_kind(&v!("1.0.0"), &v!("1.0.3")), BumpKind::Patch);
         assert_eq!(bump_kind(&v!("1.2.3"), &v!("1.2.4")), BumpKind::Patch);
+
+        assert_eq!(
+            bump_kind(&v!("1.0.0-beta.1"), &v!("1.0.0-beta.2")),
+            BumpKind::Prerelease
+        );
+        assert_eq!(
+            bump_kind(&v!("1.0.0-rc.1"), &v!("1.0.0")),
+            BumpKind::Prerelease
+        );
+        assert_eq!(
+            bump_kind(&v!("0.1.0-beta.1"), &v!("0.1.0")),
+            BumpKind::Prerelease
+        );
+    }
+
+    #[test]
+    fn prerelease_increments() {
+        assert_eq!(v!("1.0.0").increment_alpha().unwrap(), v!("1.0.0-alpha.1"));
+        assert_eq!(
+            v!("1.0.0-alpha.1").increment_alpha().unwrap(),
+            v!("1.0.0-alpha.2")
+        );
+        assert_eq!(
+            v!("1.0.0-alpha.3").increment_beta().unwrap(),
+            v!("1.0.0-beta.1")
+        );
+        assert_eq!(
+            v!("1.0.0-beta.1").increment_beta().unwrap(),
+            v!("1.0.0-beta.2")
+        );
+        assert_eq!(v!("1.0.0-beta.2").increment_rc().unwrap(), v!("1.0.0-rc.1"));
+        assert_eq!(v!("1.0.0-rc.1").increment_rc().unwrap(), v!("1.0.0-rc.2"));
+
+        assert!(v!("1.0.0-rc.1").increment_alpha().is_err());
+        assert!(v!("1.0.0-beta.1").increment_alpha().is_err());
+        assert!(v!("1.0.0-rc.1").increment_beta().is_err());
+    }
+
+    #[test]
+    fn prerelease_min_req() {
+        assert_eq!(
+            to_min_req(&v!("1.0.0-beta.1")),
+            req!("=1.0.0-beta.1")
+        );
     }
 
     #[test]
@@ -240,5 +432,14 @@ mod tests {
             updated_req(&req!("1"), &v!("1.3.4"), &v!("2.0.0")),
             SemverUpdateKind::UpdateReq(req!("2")),
         );
+
+        assert_eq!(
+            updated_req(
+                &req!("=1.0.0-beta.1"),
+                &v!("1.0.0-beta.1"),
+                &v!("1.0.0-beta.2")
+            ),
+            SemverUpdateKind::UpdateReq(req!("=1.0.0-beta.2")),
+        );
     }
 }